@@ -1,189 +1,840 @@
-#[derive(PartialEq, PartialOrd)]
-struct BigInt(Vec<u32>);
+use rand::Rng;
+
+/// A single limb of the magnitude, base 2^32.
+type BigDigit = u32;
+/// Widened type used to hold a limb-sized product or carry without overflow.
+type DoubleBigDigit = u64;
+/// One past the largest value a `BigDigit` can hold.
+const BASE: DoubleBigDigit = 1 << 32;
+/// Operand length (in limbs) above which `mul` switches from schoolbook to
+/// Karatsuba multiplication.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// The sign of a `BigInt`. `NoSign` is the only sign zero may carry, so a
+/// `BigInt` with an empty magnitude is always `NoSign`.
+#[derive(PartialEq, Clone, Copy)]
+#[allow(clippy::enum_variant_names)]
+enum Sign {
+    Minus,
+    NoSign,
+    Plus,
+}
+
+/// Magnitude stored little-endian (`0` holds the least-significant limb),
+/// the conventional layout for arbitrary-precision integers. Canonical
+/// magnitudes carry no trailing zero limbs; zero is the empty vector paired
+/// with `Sign::NoSign`.
+#[derive(PartialEq, Clone)]
+pub struct BigInt {
+    sign: Sign,
+    magnitude: Vec<BigDigit>,
+}
 
 impl BigInt {
-    fn new(value: &str) -> Self {
+    pub fn new(value: &str) -> Self {
         let mut n = BigInt::empty();
         n.set_hex(value);
         n
     }
 
     fn empty() -> Self {
-        Self(Vec::new())
+        Self { sign: Sign::NoSign, magnitude: Vec::new() }
+    }
+
+    /// Builds a `BigInt` from a magnitude, normalizing the sign to
+    /// `NoSign` whenever the magnitude is zero.
+    fn from_magnitude(sign: Sign, magnitude: Vec<BigDigit>) -> Self {
+        if magnitude.is_empty() {
+            Self::empty()
+        } else {
+            Self { sign, magnitude }
+        }
+    }
+
+    fn neg(&self) -> BigInt {
+        let sign = match self.sign {
+            Sign::Minus => Sign::Plus,
+            Sign::Plus => Sign::Minus,
+            Sign::NoSign => Sign::NoSign,
+        };
+        Self { sign, magnitude: self.magnitude.clone() }
     }
 
     fn set_hex(&mut self, hex: &str) {
-        self.0 = hex.chars().filter_map(|ch| ch.to_digit(16)).collect();
+        let (sign, digits) = match hex.strip_prefix('-') {
+            Some(rest) => (Sign::Minus, rest),
+            None => (Sign::Plus, hex),
+        };
+
+        let mut magnitude: Vec<BigDigit> = Vec::new();
+        for digit in digits.chars().filter_map(|ch| ch.to_digit(16)) {
+            let mut carry = digit as DoubleBigDigit;
+            for limb in magnitude.iter_mut() {
+                let acc = (*limb as DoubleBigDigit) * 16 + carry;
+                *limb = acc as BigDigit;
+                carry = acc >> 32;
+            }
+            if carry > 0 {
+                magnitude.push(carry as BigDigit);
+            }
+        }
+
+        self.sign = if magnitude.is_empty() { Sign::NoSign } else { sign };
+        self.magnitude = magnitude;
     }
 
-    fn get_hex(&self) -> String {
-        self.0.iter().map(|digit| format!("{:x}", digit)).collect()
+    pub fn get_hex(&self) -> String {
+        if self.magnitude.is_empty() {
+            return "0".to_string();
+        }
+
+        let mut limbs = self.magnitude.clone();
+        let mut nibbles = Vec::new();
+        while !limbs.is_empty() {
+            let mut rem: DoubleBigDigit = 0;
+            for limb in limbs.iter_mut().rev() {
+                let cur = (rem << 32) | (*limb as DoubleBigDigit);
+                *limb = (cur / 16) as BigDigit;
+                rem = cur % 16;
+            }
+            nibbles.push(rem as u32);
+            trim(&mut limbs);
+        }
+
+        nibbles.reverse();
+        let digits: String = nibbles.iter().map(|digit| format!("{:x}", digit)).collect();
+        if self.sign == Sign::Minus {
+            format!("-{digits}")
+        } else {
+            digits
+        }
     }
 
     fn xor(&self, int: &BigInt) -> BigInt {
-        let (pad_a, pad_b) = self.pad(self.0.clone(), int.0.clone());
-        let result_digits = pad_a.iter().zip(&pad_b).map(|(&a, &b)| a ^ b).collect();
-        BigInt(result_digits)
+        let (pad_a, pad_b) = pad(self.magnitude.clone(), int.magnitude.clone());
+        let mut result = pad_a.iter().zip(&pad_b).map(|(&a, &b)| a ^ b).collect();
+        trim(&mut result);
+        BigInt::from_magnitude(Sign::Plus, result)
     }
 
     fn inv(&self) -> BigInt {
-        let result_digits = self.0.iter().map(|digit| !digit ).collect();
-        BigInt(result_digits)
+        let mut result: Vec<BigDigit> = self.magnitude.iter().map(|digit| !digit).collect();
+        trim(&mut result);
+        BigInt::from_magnitude(Sign::Plus, result)
     }
 
     fn or(&self, int: &BigInt) -> BigInt {
-        let (pad_a, pad_b) = self.pad(self.0.clone(), int.0.clone());
-        let result_digits = pad_a.iter().zip(&pad_b).map(|(&a, &b)| a | b).collect();
-        BigInt(result_digits)
+        let (pad_a, pad_b) = pad(self.magnitude.clone(), int.magnitude.clone());
+        let mut result = pad_a.iter().zip(&pad_b).map(|(&a, &b)| a | b).collect();
+        trim(&mut result);
+        BigInt::from_magnitude(Sign::Plus, result)
     }
 
     fn and(&self, int: &BigInt) -> BigInt {
-        let (pad_a, pad_b) = self.pad(self.0.clone(), int.0.clone());
-        let result_digits = pad_a.iter().zip(&pad_b).map(|(&a, &b)| a & b).collect();
-        BigInt(result_digits)
+        let (pad_a, pad_b) = pad(self.magnitude.clone(), int.magnitude.clone());
+        let mut result = pad_a.iter().zip(&pad_b).map(|(&a, &b)| a & b).collect();
+        trim(&mut result);
+        BigInt::from_magnitude(Sign::Plus, result)
     }
 
     fn shift_r(&self, bits: usize) -> BigInt {
-        let mut vec = self.0.clone();
-        let shift_amount = bits % 32;
-        let carry_bits = 32 - shift_amount;
+        BigInt::from_magnitude(self.sign, shift_r_magnitude(&self.magnitude, bits))
+    }
 
-        for i in (1..vec.len()).rev() {
-            vec[i] = (vec[i] >> shift_amount) | (vec[i-1] << carry_bits);
+    fn shift_l(&self, n: u32) -> BigInt {
+        BigInt::from_magnitude(self.sign, shift_l_magnitude(&self.magnitude, n))
+    }
+
+    /// Dispatches on sign: same-sign operands add magnitudes, opposite-sign
+    /// operands subtract the smaller magnitude from the larger and take the
+    /// larger's sign.
+    fn add(&self, int: &BigInt) -> BigInt {
+        match (self.sign, int.sign) {
+            (Sign::NoSign, _) => int.clone(),
+            (_, Sign::NoSign) => self.clone(),
+            (a, b) if a == b => BigInt::from_magnitude(a, add_magnitude(&self.magnitude, &int.magnitude)),
+            _ => match cmp_magnitude(&self.magnitude, &int.magnitude) {
+                std::cmp::Ordering::Equal => BigInt::empty(),
+                std::cmp::Ordering::Greater => {
+                    BigInt::from_magnitude(self.sign, sub_magnitude(&self.magnitude, &int.magnitude))
+                }
+                std::cmp::Ordering::Less => {
+                    BigInt::from_magnitude(int.sign, sub_magnitude(&int.magnitude, &self.magnitude))
+                }
+            },
         }
+    }
 
-        vec[0] >>= shift_amount;
+    fn sub(&self, int: &BigInt) -> BigInt {
+        self.add(&int.neg())
+    }
 
-        BigInt(vec)
+    fn mul(&self, int: &BigInt) -> BigInt {
+        let sign = match (self.sign, int.sign) {
+            (Sign::NoSign, _) | (_, Sign::NoSign) => Sign::NoSign,
+            (a, b) if a == b => Sign::Plus,
+            _ => Sign::Minus,
+        };
+        BigInt::from_magnitude(sign, mul_magnitude(&self.magnitude, &int.magnitude))
     }
 
-    fn shift_l(&self, n: u32) -> BigInt {
-        let mut shifted_digits = Vec::new();
-        let mut carry = 0;
-        let bits = n % 32;
+    /// Schoolbook-only multiply, bypassing the Karatsuba dispatch in `mul`.
+    /// Exists so tests can cross-check the two paths agree; not meant to be
+    /// called outside the test module.
+    #[cfg(test)]
+    fn mul_schoolbook(&self, int: &BigInt) -> BigInt {
+        let sign = match (self.sign, int.sign) {
+            (Sign::NoSign, _) | (_, Sign::NoSign) => Sign::NoSign,
+            (a, b) if a == b => Sign::Plus,
+            _ => Sign::Minus,
+        };
+        BigInt::from_magnitude(sign, mul_schoolbook_magnitude(&self.magnitude, &int.magnitude))
+    }
 
-        for &digit in self.0.iter().rev() {
-            let shifted_digit = (digit << bits) | carry;
-            shifted_digits.push(shifted_digit);
-            carry = digit >> (32 - bits);
+    fn mod_by(&self, modulo: &BigInt) -> BigInt {
+        self.div_rem(modulo).1
+    }
+
+    /// Schoolbook long division over the limb vector (Knuth's algorithm D):
+    /// normalize so the divisor's leading limb is large, estimate each
+    /// quotient limb from the top limbs of the running remainder using a
+    /// `u64` division, then correct the estimate down by at most two before
+    /// subtracting. The quotient's sign follows the usual rule and the
+    /// remainder takes the dividend's sign.
+    fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        if divisor.magnitude.is_empty() {
+            panic!("attempted to divide by zero");
+        }
+
+        let (quotient_mag, remainder_mag) = div_rem_magnitude(&self.magnitude, &divisor.magnitude);
+
+        let quotient_sign = match (self.sign, divisor.sign) {
+            (Sign::NoSign, _) => Sign::NoSign,
+            (a, b) if a == b => Sign::Plus,
+            _ => Sign::Minus,
+        };
+
+        (
+            BigInt::from_magnitude(quotient_sign, quotient_mag),
+            BigInt::from_magnitude(self.sign, remainder_mag),
+        )
+    }
+
+    /// Computes `self^exponent mod modulus` by square-and-multiply. Assumes
+    /// non-negative operands, matching the RSA-style use this crate targets.
+    /// For an odd modulus this runs entirely in Montgomery form so each
+    /// squaring is a limb-wise reduction instead of a full division; even
+    /// moduli fall back to plain squaring through `mod_by`.
+    fn pow_mod(&self, exponent: &BigInt, modulus: &BigInt) -> BigInt {
+        if exponent.magnitude.is_empty() {
+            return BigInt::new("1").mod_by(modulus);
+        }
+        if modulus.magnitude[0] & 1 == 0 {
+            return self.pow_mod_plain(exponent, modulus);
         }
 
-        if carry > 0 {
-            shifted_digits.push(carry);
+        let k = modulus.magnitude.len();
+        let r_bits = (k as u32) * 32;
+        let n0inv = 0u32.wrapping_sub(inv_mod_base(modulus.magnitude[0]));
+
+        let mont_one = BigInt::new("1").shift_l(r_bits).mod_by(modulus);
+        let mut base = self.mod_by(modulus).shift_l(r_bits).mod_by(modulus);
+        let mut result = mont_one;
+
+        for &limb in &exponent.magnitude {
+            for bit in 0..32 {
+                if (limb >> bit) & 1 == 1 {
+                    result = result.mul(&base).montgomery_reduce(modulus, n0inv, k);
+                }
+                base = base.mul(&base).montgomery_reduce(modulus, n0inv, k);
+            }
         }
 
-        shifted_digits.reverse();
-        BigInt(shifted_digits)
+        result.montgomery_reduce(modulus, n0inv, k)
     }
 
-    fn pad(&self, a: Vec<u32>, b: Vec<u32>) -> (Vec<u32>, Vec<u32>) {
-        let (mut pad_a, mut pad_b) = (a.clone(), b.clone());
-        if a.len() > b.len() {
-            while pad_b.len() != a.len() {
-                pad_b.insert(0, 0);
+    fn pow_mod_plain(&self, exponent: &BigInt, modulus: &BigInt) -> BigInt {
+        let mut result = BigInt::new("1").mod_by(modulus);
+        let mut base = self.mod_by(modulus);
+
+        for &limb in &exponent.magnitude {
+            for bit in 0..32 {
+                if (limb >> bit) & 1 == 1 {
+                    result = result.mul(&base).mod_by(modulus);
+                }
+                base = base.mul(&base).mod_by(modulus);
             }
         }
-        if a.len() < b.len() {
-            while pad_a.len() != b.len() {
-                pad_a.insert(0, 0);
+
+        result
+    }
+
+    /// Montgomery reduction (REDC): given a product `t` computed in
+    /// Montgomery form, folds it back down by `n0inv = -modulus^-1 mod 2^32`
+    /// one limb at a time so the result is `t / R mod modulus`.
+    fn montgomery_reduce(&self, modulus: &BigInt, n0inv: u32, k: usize) -> BigInt {
+        let mut t = self.magnitude.clone();
+        t.resize(2 * k + 1, 0);
+
+        for i in 0..k {
+            let m = t[i].wrapping_mul(n0inv);
+            let mut carry: DoubleBigDigit = 0;
+            for j in 0..k {
+                let n_j = modulus.magnitude.get(j).copied().unwrap_or(0);
+                let acc = m as DoubleBigDigit * n_j as DoubleBigDigit + t[i + j] as DoubleBigDigit + carry;
+                t[i + j] = acc as BigDigit;
+                carry = acc >> 32;
+            }
+            let mut idx = i + k;
+            while carry > 0 {
+                let acc = t[idx] as DoubleBigDigit + carry;
+                t[idx] = acc as BigDigit;
+                carry = acc >> 32;
+                idx += 1;
             }
         }
-        (pad_a, pad_b)
+
+        let mut result = t[k..].to_vec();
+        trim(&mut result);
+        let result = BigInt::from_magnitude(Sign::Plus, result);
+
+        if result >= *modulus {
+            result.sub(modulus)
+        } else {
+            result
+        }
     }
 
-    fn add(&self, int: &BigInt) -> BigInt {
-        let (mut carry, mut result_digits) = (0, Vec::new());
+    /// Miller-Rabin primality test, `rounds` independent random witnesses.
+    /// Writes `n-1 = 2^s * d` with `d` odd, then for each witness `a` checks
+    /// `a^d mod n` against `1`/`n-1` and the squaring chain in between;
+    /// `n` is declared composite as soon as a witness refutes it.
+    pub fn is_probable_prime(&self, rounds: usize) -> bool {
+        let one = BigInt::new("1");
+        let two = BigInt::new("2");
+        let three = BigInt::new("3");
+        let four = BigInt::new("4");
 
-        let (pad_a, pad_b) = self.pad(self.0.clone(), int.0.clone());
-        for (a, b) in pad_a.iter().rev().zip(pad_b.iter().rev()) {
-            let sum = a + b + carry;
-            let digit = sum % 0x10;
-            carry = sum / 0x10;
-            result_digits.push(digit);
+        if *self < four {
+            return *self == two || *self == three;
+        }
+        if self.magnitude[0] & 1 == 0 {
+            return false;
         }
 
-        if carry > 0 {
-            result_digits.push(carry);
+        let n_minus_one = self.sub(&one);
+        let mut d = n_minus_one.clone();
+        let mut s = 0u32;
+        while d.magnitude[0] & 1 == 0 {
+            d = d.shift_r(1);
+            s += 1;
         }
 
-        result_digits.reverse();
-        BigInt(result_digits)
-    }
+        let n_minus_two = self.sub(&two);
+        let mut rng = rand::thread_rng();
 
-    fn sub(&self, int: &BigInt) -> BigInt {
-        let (pad_a, pad_b) = self.pad(self.0.clone(), int.0.clone());
-        let mut result_digits = Vec::new();
-        let mut borrow = false;
-
-        for (a, b) in pad_a.iter().rev().zip(pad_b.iter().rev()) {
-            let a = if borrow {
-                if *a == 0 {
-                    borrow = true;
-                    0xF
-                } else {
-                    borrow = false;
-                    a - 1
+        'witnesses: for _ in 0..rounds {
+            let a = random_in_range(&two, &n_minus_two, &mut rng);
+            let mut x = a.pow_mod(&d, self);
+            if x == one || x == n_minus_one {
+                continue;
+            }
+
+            for _ in 0..s.saturating_sub(1) {
+                x = x.mul(&x).mod_by(self);
+                if x == n_minus_one {
+                    continue 'witnesses;
                 }
-            } else {
-                *a
-            };
-            let diff = if a < *b {
-                borrow = true;
-                0x10 + a - b
-            } else {
-                a - b
-            };
-            result_digits.push(diff);
+            }
+
+            return false;
+        }
+
+        true
+    }
+
+    /// Serializes the magnitude as big-endian bytes, most-significant byte
+    /// first, with no leading zero byte (the empty magnitude serializes to
+    /// an empty `Vec`). Unsigned: there is no byte encoding for the sign, so
+    /// this panics on a negative `BigInt` rather than silently returning the
+    /// wrong value; negate first if you need the magnitude of a negative.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        assert!(self.sign != Sign::Minus, "to_bytes_be: cannot serialize a negative BigInt as unsigned bytes");
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Serializes the magnitude as little-endian bytes, least-significant
+    /// byte first, with no trailing zero byte. Unsigned: panics on a
+    /// negative `BigInt` for the same reason as `to_bytes_be`.
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        assert!(self.sign != Sign::Minus, "to_bytes_le: cannot serialize a negative BigInt as unsigned bytes");
+        let mut bytes: Vec<u8> = self.magnitude.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        while bytes.last() == Some(&0) {
+            bytes.pop();
         }
+        bytes
+    }
+
+    /// Parses a big-endian byte string (most-significant byte first) into a
+    /// non-negative `BigInt`. Inverse of `to_bytes_be`.
+    pub fn from_bytes_be(bytes: &[u8]) -> BigInt {
+        let mut reversed = bytes.to_vec();
+        reversed.reverse();
+        BigInt::from_bytes_le(&reversed)
+    }
+
+    /// Parses a little-endian byte string (least-significant byte first)
+    /// into a non-negative `BigInt`. Inverse of `to_bytes_le`.
+    pub fn from_bytes_le(bytes: &[u8]) -> BigInt {
+        let mut magnitude: Vec<BigDigit> = bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut limb = [0u8; 4];
+                limb[..chunk.len()].copy_from_slice(chunk);
+                BigDigit::from_le_bytes(limb)
+            })
+            .collect();
+        trim(&mut magnitude);
+        BigInt::from_magnitude(Sign::Plus, magnitude)
+    }
 
-        if borrow {
-            panic!("Attempted to subtract a larger number from a smaller number");
+    /// Greatest common divisor via the extended Euclidean algorithm,
+    /// keeping only the `r` half of the recurrence (see `mod_inverse` for
+    /// the Bezout coefficient).
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut old_r, mut r) = (self.clone(), other.clone());
+
+        while r != BigInt::empty() {
+            let (_, remainder) = old_r.div_rem(&r);
+            old_r = r;
+            r = remainder;
         }
 
-        result_digits.reverse();
-        BigInt(result_digits)
+        old_r
     }
 
-    fn mul(&self, int: &BigInt) -> BigInt {
-        let mut result = BigInt::empty();
-        let (pad_a, pad_b) = self.pad(self.0.clone(), int.0.clone());
-
-        for (i, &a) in pad_a.iter().enumerate().rev() {
-            let mut carry = 0;
-            let mut temp_result = vec![0; pad_a.len() - i - 1];
-
-            for &b in pad_b.iter().rev() {
-                let product = a * b + carry;
-                let digit = product % 0x10;
-                carry = product / 0x10;
-                temp_result.push(digit);
+    /// Modular inverse via the extended Euclidean algorithm: maintains
+    /// `(old_r, r)` and the paired Bezout coefficients `(old_s, s)`, where
+    /// `old_r = old_s * self + k * modulus` for some `k`; once `r` reaches
+    /// zero, `old_r` is the gcd and `old_s` is the coefficient. Returns
+    /// `None` when `self` and `modulus` aren't coprime.
+    pub fn mod_inverse(&self, modulus: &BigInt) -> Option<BigInt> {
+        let (mut old_r, mut r) = (self.clone(), modulus.clone());
+        let (mut old_s, mut s) = (BigInt::new("1"), BigInt::empty());
+
+        while r != BigInt::empty() {
+            let (q, remainder) = old_r.div_rem(&r);
+            old_r = r;
+            r = remainder;
+
+            let new_s = old_s.sub(&q.mul(&s));
+            old_s = s;
+            s = new_s;
+        }
+
+        if old_r != BigInt::new("1") {
+            return None;
+        }
+
+        // `mod_by` truncates toward zero and keeps the dividend's sign, so
+        // a negative coefficient needs one more modulus added to land in
+        // the canonical `[0, modulus)` range.
+        let remainder = old_s.mod_by(modulus);
+        Some(if remainder.sign == Sign::Minus { remainder.add(modulus) } else { remainder })
+    }
+
+    /// Computes the RSA modulus `n = p*q` for two (presumably distinct)
+    /// primes. A thin wrapper so callers outside this module can build an
+    /// RSA modulus without reaching for the private `mul`.
+    pub fn rsa_modulus(p: &BigInt, q: &BigInt) -> BigInt {
+        p.mul(q)
+    }
+
+    /// Computes Euler's totient of an RSA modulus `n = p*q` for two
+    /// distinct primes: `phi(n) = (p-1)*(q-1)`. `mod_inverse` is then used
+    /// to derive the private exponent `d = e^-1 mod phi(n)`.
+    pub fn rsa_totient(p: &BigInt, q: &BigInt) -> BigInt {
+        let one = BigInt::new("1");
+        p.sub(&one).mul(&q.sub(&one))
+    }
+}
+
+/// Small-prime table used to cheaply reject most composite candidates
+/// before paying for a Miller-Rabin round.
+const SMALL_PRIMES: &[u32] = &[
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// Samples random odd candidates of the requested bit length (top and
+/// bottom bits forced set), trial-divides by `SMALL_PRIMES`, and runs
+/// Miller-Rabin until one passes.
+pub fn random_prime(bits: u64) -> BigInt {
+    let mut rng = rand::thread_rng();
+
+    loop {
+        let candidate = random_odd_candidate(bits, &mut rng);
+
+        if SMALL_PRIMES.iter().any(|&p| {
+            let prime = BigInt::new(&format!("{p:x}"));
+            candidate != prime && candidate.mod_by(&prime) == BigInt::empty()
+        }) {
+            continue;
+        }
+
+        if candidate.is_probable_prime(20) {
+            return candidate;
+        }
+    }
+}
+
+/// Generates a uniformly random magnitude of exactly `bits` bits with the
+/// top bit (to fix the bit length) and bottom bit (to force it odd) set.
+fn random_odd_candidate(bits: u64, rng: &mut impl Rng) -> BigInt {
+    let limb_count = bits.div_ceil(32) as usize;
+    let mut magnitude: Vec<BigDigit> = (0..limb_count).map(|_| rng.gen()).collect();
+
+    let top_bit = (bits - 1) % 32;
+    let last = limb_count - 1;
+    magnitude[last] &= ((1u64 << (top_bit + 1)) - 1) as BigDigit;
+    magnitude[last] |= 1 << top_bit;
+    magnitude[0] |= 1;
+
+    BigInt::from_magnitude(Sign::Plus, magnitude)
+}
+
+/// Returns the bit length of a canonical magnitude (`0` for the empty
+/// magnitude).
+fn bit_length(magnitude: &[BigDigit]) -> u64 {
+    match magnitude.last() {
+        None => 0,
+        Some(top) => (magnitude.len() as u64 - 1) * 32 + (32 - top.leading_zeros() as u64),
+    }
+}
+
+/// Draws a uniformly random `BigInt` in `[low, high]` by rejection sampling
+/// over the bit length of the inclusive span.
+fn random_in_range(low: &BigInt, high: &BigInt, rng: &mut impl Rng) -> BigInt {
+    let span = high.sub(low).add(&BigInt::new("1"));
+    let bits = bit_length(&span.magnitude);
+
+    loop {
+        let limb_count = bits.div_ceil(32).max(1) as usize;
+        let mut magnitude: Vec<BigDigit> = (0..limb_count).map(|_| rng.gen()).collect();
+        if !bits.is_multiple_of(32) {
+            let mask = (1u32 << (bits % 32)) - 1;
+            *magnitude.last_mut().unwrap() &= mask;
+        }
+        trim(&mut magnitude);
+        let offset = BigInt::from_magnitude(Sign::Plus, magnitude);
+
+        if offset < span {
+            return low.add(&offset);
+        }
+    }
+}
+
+/// Compares two canonical (no trailing zero limb) little-endian magnitudes.
+fn cmp_magnitude(a: &[BigDigit], b: &[BigDigit]) -> std::cmp::Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+impl PartialOrd for BigInt {
+    /// Orders by sign first, then by magnitude (reversed for two negatives).
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        fn rank(sign: Sign) -> i8 {
+            match sign {
+                Sign::Minus => -1,
+                Sign::NoSign => 0,
+                Sign::Plus => 1,
             }
+        }
 
-            if carry > 0 {
-                temp_result.push(carry);
+        Some(match rank(self.sign).cmp(&rank(other.sign)) {
+            std::cmp::Ordering::Equal => {
+                let magnitude_order = cmp_magnitude(&self.magnitude, &other.magnitude);
+                if self.sign == Sign::Minus {
+                    magnitude_order.reverse()
+                } else {
+                    magnitude_order
+                }
             }
+            sign_order => sign_order,
+        })
+    }
+}
+
+fn trim(limbs: &mut Vec<BigDigit>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn pad(mut a: Vec<BigDigit>, mut b: Vec<BigDigit>) -> (Vec<BigDigit>, Vec<BigDigit>) {
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    (a, b)
+}
+
+fn add_magnitude(a: &[BigDigit], b: &[BigDigit]) -> Vec<BigDigit> {
+    let (pad_a, pad_b) = pad(a.to_vec(), b.to_vec());
+    let mut result = Vec::with_capacity(pad_a.len() + 1);
+    let mut carry: DoubleBigDigit = 0;
+
+    for (a, b) in pad_a.iter().zip(pad_b.iter()) {
+        let sum = *a as DoubleBigDigit + *b as DoubleBigDigit + carry;
+        result.push(sum as BigDigit);
+        carry = sum >> 32;
+    }
 
-            temp_result.reverse();
-            let temp_result_bigint = BigInt(temp_result);
+    if carry > 0 {
+        result.push(carry as BigDigit);
+    }
+
+    result
+}
 
-            result = result.add(&temp_result_bigint);
+/// Subtracts `b` from `a`; the caller must ensure `a >= b` in magnitude.
+fn sub_magnitude(a: &[BigDigit], b: &[BigDigit]) -> Vec<BigDigit> {
+    let (pad_a, pad_b) = pad(a.to_vec(), b.to_vec());
+    let mut result = Vec::with_capacity(pad_a.len());
+    let mut borrow: i64 = 0;
+
+    for (a, b) in pad_a.iter().zip(pad_b.iter()) {
+        let diff = *a as i64 - *b as i64 - borrow;
+        if diff < 0 {
+            result.push((diff + BASE as i64) as BigDigit);
+            borrow = 1;
+        } else {
+            result.push(diff as BigDigit);
+            borrow = 0;
         }
-        result
     }
 
-    fn mod_by(&self, modulo: &BigInt) -> BigInt {
-        let mut result = self.sub(modulo);
+    if borrow != 0 {
+        panic!("Attempted to subtract a larger number from a smaller number");
+    }
+
+    trim(&mut result);
+    result
+}
 
-        let zero = BigInt::empty();
-        while result >= *modulo || result < zero {
-            if result >= *modulo {
-                result = result.sub(modulo);
+fn mul_magnitude(a: &[BigDigit], b: &[BigDigit]) -> Vec<BigDigit> {
+    if a.len() > KARATSUBA_THRESHOLD && b.len() > KARATSUBA_THRESHOLD {
+        return mul_karatsuba_magnitude(a, b);
+    }
+    mul_schoolbook_magnitude(a, b)
+}
+
+fn mul_schoolbook_magnitude(a: &[BigDigit], b: &[BigDigit]) -> Vec<BigDigit> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = vec![0; a.len() + b.len()];
+    for (i, &a) in a.iter().enumerate() {
+        let mut carry: DoubleBigDigit = 0;
+        for (j, &b) in b.iter().enumerate() {
+            let acc = result[i + j] as DoubleBigDigit + a as DoubleBigDigit * b as DoubleBigDigit + carry;
+            result[i + j] = acc as BigDigit;
+            carry = acc >> 32;
+        }
+
+        let mut k = i + b.len();
+        while carry > 0 {
+            let acc = result[k] as DoubleBigDigit + carry;
+            result[k] = acc as BigDigit;
+            carry = acc >> 32;
+            k += 1;
+        }
+    }
+
+    trim(&mut result);
+    result
+}
+
+/// Splits `a = a1*B^m + a0`, `b = b1*B^m + b0` at half the shorter operand's
+/// length and combines `z2*B^2m + z1*B^m + z0`, recursing back through
+/// `mul_magnitude` (and so back to the schoolbook base case below the
+/// threshold).
+fn mul_karatsuba_magnitude(a: &[BigDigit], b: &[BigDigit]) -> Vec<BigDigit> {
+    let m = a.len().min(b.len()) / 2;
+    let (a0, a1) = split_at_limb(a, m);
+    let (b0, b1) = split_at_limb(b, m);
+
+    let z0 = mul_magnitude(&a0, &b0);
+    let z2 = mul_magnitude(&a1, &b1);
+    let z1 = sub_magnitude(
+        &sub_magnitude(&mul_magnitude(&add_magnitude(&a0, &a1), &add_magnitude(&b0, &b1)), &z0),
+        &z2,
+    );
+
+    let shift = (m as u32) * 32;
+    add_magnitude(&add_magnitude(&shift_l_magnitude(&z2, 2 * shift), &shift_l_magnitude(&z1, shift)), &z0)
+}
+
+/// Splits a little-endian magnitude into `(low, high)` at limb index `m`,
+/// i.e. `magnitude = high*B^m + low`.
+fn split_at_limb(magnitude: &[BigDigit], m: usize) -> (Vec<BigDigit>, Vec<BigDigit>) {
+    if m >= magnitude.len() {
+        return (magnitude.to_vec(), Vec::new());
+    }
+    let mut low = magnitude[..m].to_vec();
+    let mut high = magnitude[m..].to_vec();
+    trim(&mut low);
+    trim(&mut high);
+    (low, high)
+}
+
+fn shift_l_magnitude(magnitude: &[BigDigit], n: u32) -> Vec<BigDigit> {
+    if magnitude.is_empty() || n == 0 {
+        return magnitude.to_vec();
+    }
+
+    let limb_shift = (n / 32) as usize;
+    let bit_shift = n % 32;
+
+    let mut result = vec![0; limb_shift];
+    let mut carry = 0;
+    for &limb in magnitude {
+        if bit_shift == 0 {
+            result.push(limb);
+        } else {
+            result.push((limb << bit_shift) | carry);
+            carry = limb >> (32 - bit_shift);
+        }
+    }
+    if carry > 0 {
+        result.push(carry);
+    }
+
+    result
+}
+
+fn shift_r_magnitude(magnitude: &[BigDigit], bits: usize) -> Vec<BigDigit> {
+    let limb_shift = bits / 32;
+    if limb_shift >= magnitude.len() {
+        return Vec::new();
+    }
+    let bit_shift = (bits % 32) as u32;
+
+    let mut result = magnitude[limb_shift..].to_vec();
+    if bit_shift > 0 {
+        for i in 0..result.len() {
+            let hi = if i + 1 < result.len() { result[i + 1] } else { 0 };
+            result[i] = (result[i] >> bit_shift) | (hi << (32 - bit_shift));
+        }
+    }
+
+    trim(&mut result);
+    result
+}
+
+/// Divides a little-endian magnitude by a single nonzero limb.
+fn div_rem_by_limb(limbs: &[BigDigit], divisor: BigDigit) -> (Vec<BigDigit>, BigDigit) {
+    let mut quotient = vec![0; limbs.len()];
+    let mut rem: DoubleBigDigit = 0;
+    for i in (0..limbs.len()).rev() {
+        let cur = (rem << 32) | limbs[i] as DoubleBigDigit;
+        quotient[i] = (cur / divisor as DoubleBigDigit) as BigDigit;
+        rem = cur % divisor as DoubleBigDigit;
+    }
+    trim(&mut quotient);
+    (quotient, rem as BigDigit)
+}
+
+/// Schoolbook long division over magnitudes (Knuth's algorithm D): normalize
+/// so the divisor's leading limb is large, estimate each quotient limb from
+/// the top limbs of the running remainder using a `u64` division, then
+/// correct the estimate down by at most two before subtracting.
+fn div_rem_magnitude(dividend: &[BigDigit], divisor: &[BigDigit]) -> (Vec<BigDigit>, Vec<BigDigit>) {
+    if divisor.is_empty() {
+        panic!("attempted to divide by zero");
+    }
+    if cmp_magnitude(dividend, divisor) == std::cmp::Ordering::Less {
+        return (Vec::new(), dividend.to_vec());
+    }
+    if divisor.len() == 1 {
+        let (quotient, remainder) = div_rem_by_limb(dividend, divisor[0]);
+        return (quotient, if remainder == 0 { vec![] } else { vec![remainder] });
+    }
+
+    let shift = divisor.last().unwrap().leading_zeros();
+    let divisor_norm = shift_l_magnitude(divisor, shift);
+    let mut remainder = shift_l_magnitude(dividend, shift);
+    remainder.push(0);
+
+    let n = divisor_norm.len();
+    let m = remainder.len() - n;
+    let mut quotient = vec![0; m];
+
+    for j in (0..m).rev() {
+        let top = ((remainder[j + n] as DoubleBigDigit) << 32) | remainder[j + n - 1] as DoubleBigDigit;
+        let mut qhat = top / divisor_norm[n - 1] as DoubleBigDigit;
+        let mut rhat = top % divisor_norm[n - 1] as DoubleBigDigit;
+
+        while qhat >= BASE
+            || qhat * divisor_norm[n - 2] as DoubleBigDigit > (rhat << 32) | remainder[j + n - 2] as DoubleBigDigit
+        {
+            qhat -= 1;
+            rhat += divisor_norm[n - 1] as DoubleBigDigit;
+            if rhat >= BASE {
+                break;
+            }
+        }
+
+        let mut borrow: i64 = 0;
+        let mut carry: DoubleBigDigit = 0;
+        for i in 0..n {
+            let product = qhat * divisor_norm[i] as DoubleBigDigit + carry;
+            carry = product >> 32;
+            let diff = remainder[j + i] as i64 - (product as BigDigit) as i64 - borrow;
+            if diff < 0 {
+                remainder[j + i] = (diff + BASE as i64) as BigDigit;
+                borrow = 1;
             } else {
-                result = result.add(modulo);
+                remainder[j + i] = diff as BigDigit;
+                borrow = 0;
             }
         }
-        result
+        let top_diff = remainder[j + n] as i64 - carry as i64 - borrow;
+
+        if top_diff < 0 {
+            // The estimate was one too high: add the divisor back once.
+            qhat -= 1;
+            let mut carryback: DoubleBigDigit = 0;
+            for i in 0..n {
+                let sum = remainder[j + i] as DoubleBigDigit + divisor_norm[i] as DoubleBigDigit + carryback;
+                remainder[j + i] = sum as BigDigit;
+                carryback = sum >> 32;
+            }
+            remainder[j + n] = (top_diff + BASE as i64) as BigDigit;
+        } else {
+            remainder[j + n] = top_diff as BigDigit;
+        }
+
+        quotient[j] = qhat as BigDigit;
+    }
+
+    trim(&mut quotient);
+    remainder.truncate(n);
+    trim(&mut remainder);
+    let remainder = shift_r_magnitude(&remainder, shift as usize);
+
+    (quotient, remainder)
+}
+
+/// Computes the inverse of an odd limb modulo `2^32` via Newton's iteration
+/// (each step doubles the number of correct bits, so five steps take the
+/// single correct starting bit to all 32).
+fn inv_mod_base(n0: BigDigit) -> BigDigit {
+    let mut x: BigDigit = 1;
+    for _ in 0..5 {
+        x = x.wrapping_mul(2u32.wrapping_sub(n0.wrapping_mul(x)));
     }
+    x
 }
 
 #[cfg(test)]
@@ -235,6 +886,33 @@ mod tests {
         )
     }
 
+    #[test]
+    fn sub_negative_result() {
+        let number_a = BigInt::new("10");
+        let number_b = BigInt::new("20");
+
+        let result = number_a.sub(&number_b);
+        assert_eq!("-10", result.get_hex());
+    }
+
+    #[test]
+    fn add_mixed_signs() {
+        let positive = BigInt::new("100");
+        let negative = BigInt::new("-40");
+
+        assert_eq!("c0", positive.add(&negative).get_hex());
+        assert_eq!("-140", negative.add(&positive.neg()).get_hex());
+    }
+
+    #[test]
+    fn ordering_respects_sign() {
+        let negative = BigInt::new("-ff");
+        let positive = BigInt::new("1");
+
+        assert!(negative < positive);
+        assert!(BigInt::new("-ff") < BigInt::new("-1"));
+    }
+
     #[test]
     fn mul() {
         let hex_a = "7d7deab2affa38154326e96d350deee1";
@@ -250,6 +928,21 @@ mod tests {
         )
     }
 
+    #[test]
+    fn mul_karatsuba_matches_schoolbook() {
+        // Both operands are well past KARATSUBA_THRESHOLD limbs, so this
+        // exercises the recursive split; cross-check against the plain
+        // schoolbook path instead of a hand-computed product.
+        let hex_a = "7d7deab2affa38154326e96d350deee1".repeat(20);
+        let hex_b = "97f92a75b3faf8939e8e98b96476fd22".repeat(20);
+        let number_a = BigInt::new(&hex_a);
+        let number_b = BigInt::new(&hex_b);
+
+        let karatsuba = number_a.mul(&number_b);
+        let schoolbook = number_a.mul_schoolbook(&number_b);
+        assert_eq!(karatsuba.get_hex(), schoolbook.get_hex());
+    }
+
     #[test]
     fn modulus() {
         let hex_a = "abcdef";
@@ -260,18 +953,62 @@ mod tests {
 
         let result = number_a.mod_by(&number_b);
         assert_eq!(
-            "07f6e9",
+            "7f6e9",
             result.get_hex()
         )
     }
 
+    #[test]
+    fn div_rem() {
+        let hex_a = "abcdef";
+        let number_a = BigInt::new(hex_a);
+
+        let hex_b = "123456";
+        let number_b = BigInt::new(hex_b);
+
+        let (quotient, remainder) = number_a.div_rem(&number_b);
+        assert_eq!("9", quotient.get_hex());
+        assert_eq!("7f6e9", remainder.get_hex());
+    }
+
+    #[test]
+    fn div_rem_dividend_smaller_than_divisor() {
+        let number_a = BigInt::new("1234");
+        let number_b = BigInt::new("abcdef");
+
+        let (quotient, remainder) = number_a.div_rem(&number_b);
+        assert_eq!("0", quotient.get_hex());
+        assert_eq!("1234", remainder.get_hex());
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_rem_by_zero_panics() {
+        let number_a = BigInt::new("1234");
+        let zero = BigInt::new("0");
+        number_a.div_rem(&zero);
+    }
+
+    #[test]
+    fn div_rem_multi_limb_divisor() {
+        let number_a = BigInt::new("123456789abcdef0123456789abcdef0123456789abcdef");
+        let number_b = BigInt::new("fedcba9876543210fedcba9876543210");
+
+        let (quotient, remainder) = number_a.div_rem(&number_b);
+        assert_eq!(
+            quotient.mul(&number_b).add(&remainder).get_hex(),
+            number_a.get_hex()
+        );
+        assert!(remainder < number_b);
+    }
+
     #[test]
     fn test_and() {
         let number_a = BigInt::new("ff");
         let number_b = BigInt::new("0f");
         let result = number_a.and(&number_b);
         assert_eq!(
-            "0f",
+            "f",
             result.get_hex()
         );
     }
@@ -290,4 +1027,120 @@ mod tests {
         let result = number_a.shift_l(4);
         assert_eq!("f0", result.get_hex());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn pow_mod_odd_modulus() {
+        // 7^560 mod 561 == 1 (561 is a Carmichael number, a classic check)
+        let base = BigInt::new("7");
+        let exponent = BigInt::new("230");
+        let modulus = BigInt::new("231");
+
+        let result = base.pow_mod(&exponent, &modulus);
+        assert_eq!("1", result.get_hex());
+    }
+
+    #[test]
+    fn pow_mod_even_modulus_falls_back() {
+        let base = BigInt::new("5");
+        let exponent = BigInt::new("d"); // 13
+        let modulus = BigInt::new("40"); // 64, even
+
+        let result = base.pow_mod(&exponent, &modulus);
+        assert_eq!("15", result.get_hex());
+    }
+
+    #[test]
+    fn pow_mod_zero_exponent() {
+        let base = BigInt::new("123456789abcdef");
+        let exponent = BigInt::new("0");
+        let modulus = BigInt::new("97");
+
+        let result = base.pow_mod(&exponent, &modulus);
+        assert_eq!("1", result.get_hex());
+    }
+
+    #[test]
+    fn is_probable_prime_known_primes() {
+        // 2, 3, and the Mersenne prime 2^31 - 1.
+        assert!(BigInt::new("2").is_probable_prime(20));
+        assert!(BigInt::new("3").is_probable_prime(20));
+        assert!(BigInt::new("7fffffff").is_probable_prime(20));
+    }
+
+    #[test]
+    fn is_probable_prime_known_composites() {
+        assert!(!BigInt::new("0").is_probable_prime(20));
+        assert!(!BigInt::new("1").is_probable_prime(20));
+        assert!(!BigInt::new("4").is_probable_prime(20));
+        // 561 = 3 * 11 * 17, the smallest Carmichael number.
+        assert!(!BigInt::new("231").is_probable_prime(20));
+    }
+
+    #[test]
+    fn random_prime_is_probable_prime_of_requested_size() {
+        let prime = super::random_prime(64);
+        assert!(prime.is_probable_prime(20));
+        assert!(super::bit_length(&prime.magnitude) == 64);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let number = BigInt::new("123456789abcdef0123456789abcdef0");
+
+        let be = number.to_bytes_be();
+        assert_eq!(BigInt::from_bytes_be(&be).get_hex(), number.get_hex());
+
+        let le = number.to_bytes_le();
+        assert_eq!(BigInt::from_bytes_le(&le).get_hex(), number.get_hex());
+
+        assert_eq!(be.iter().rev().copied().collect::<Vec<u8>>(), le);
+    }
+
+    #[test]
+    fn bytes_of_zero_are_empty() {
+        let zero = BigInt::new("0");
+        assert!(zero.to_bytes_be().is_empty());
+        assert!(zero.to_bytes_le().is_empty());
+        assert_eq!("0", BigInt::from_bytes_be(&[]).get_hex());
+    }
+
+    #[test]
+    fn bytes_be_drops_leading_zero_byte() {
+        // "00abcdef" has a leading zero byte that must not appear in the
+        // serialized form.
+        let number = BigInt::new("00abcdef");
+        assert_eq!(number.to_bytes_be(), vec![0xab, 0xcd, 0xef]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bytes_be_of_negative_panics() {
+        BigInt::new("-abcdef").to_bytes_be();
+    }
+
+    #[test]
+    #[should_panic]
+    fn bytes_le_of_negative_panics() {
+        BigInt::new("-abcdef").to_bytes_le();
+    }
+
+    #[test]
+    fn gcd_of_coprime_and_shared_factor() {
+        assert_eq!("1", BigInt::new("11").gcd(&BigInt::new("a")).get_hex());
+        // gcd(54, 24) = 6
+        assert_eq!("6", BigInt::new("36").gcd(&BigInt::new("18")).get_hex());
+    }
+
+    #[test]
+    fn mod_inverse_of_coprime_pair() {
+        // 3 * 4 = 12 = 11*1 + 1, so 3^-1 mod 11 is 4.
+        let inverse = BigInt::new("3").mod_inverse(&BigInt::new("b")).unwrap();
+        assert_eq!("4", inverse.get_hex());
+        assert_eq!("1", BigInt::new("3").mul(&inverse).mod_by(&BigInt::new("b")).get_hex());
+    }
+
+    #[test]
+    fn mod_inverse_of_non_coprime_pair_is_none() {
+        assert!(BigInt::new("6").mod_inverse(&BigInt::new("9")).is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::time::Instant;
 
+use large_numbers::BigInt;
 use num_bigint::{BigUint, RandomBits};
 use rand::Rng;
 
@@ -14,6 +15,35 @@ fn generate_key(length: u64) -> BigUint {
     key
 }
 
+/// Generates a probable prime of the requested bit length via Miller-Rabin,
+/// the actual key material this binary is meant to produce.
+fn generate_prime_key(length: u64) -> BigInt {
+    large_numbers::random_prime(length)
+}
+
+/// Generates an RSA keypair of roughly `bits` total size: two half-length
+/// probable primes, the conventional public exponent `e = 65537`, and the
+/// private exponent `d = e^-1 mod phi(n)` via the extended-Euclidean
+/// `mod_inverse`. Retries with fresh primes on the rare case `e` and
+/// `phi(n)` aren't coprime.
+fn generate_rsa_keypair(bits: u64) -> (BigInt, BigInt, BigInt) {
+    let e = BigInt::new("10001"); // 65537
+
+    loop {
+        let p = generate_prime_key(bits / 2);
+        let q = generate_prime_key(bits / 2);
+        let phi = BigInt::rsa_totient(&p, &q);
+
+        if e.gcd(&phi).get_hex() != "1" {
+            continue;
+        }
+
+        let n = BigInt::rsa_modulus(&p, &q);
+        let d = e.mod_inverse(&phi).expect("gcd(e, phi) == 1 checked above");
+        return (n, e, d);
+    }
+}
+
 fn find_same_key(length: u64, key: &BigUint) {
     let mut found_key = BigUint::default();
     let start = Instant::now();
@@ -43,6 +73,23 @@ fn main() {
             length = length,
             key = key,
         );
+
+        let prime = generate_prime_key(*length as u64);
+        println!(
+            "Probable prime for {length} bits is 0x{hex} (is_probable_prime: {probable})\n",
+            length = length,
+            hex = prime.get_hex(),
+            probable = prime.is_probable_prime(20),
+        );
+
+        let (n, e, d) = generate_rsa_keypair(*length as u64);
+        println!(
+            "RSA keypair for {length} bits: n=0x{n_hex}, e=0x{e_hex}, d=0x{d_hex}\n",
+            length = length,
+            n_hex = n.get_hex(),
+            e_hex = e.get_hex(),
+            d_hex = d.get_hex(),
+        );
     }
     for length in lengths {
         find_same_key(length as u64, initial_keys.get(&length).unwrap());